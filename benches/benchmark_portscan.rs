@@ -2,7 +2,7 @@ use async_std::task::block_on;
 use criterion::{criterion_group, criterion_main, Criterion};
 use rustscan::input::{Opts, PortRange, ScanOrder};
 use rustscan::port_strategy::PortStrategy;
-use rustscan::scanner::Scanner;
+use rustscan::scanner::{ScanOptions, Scanner};
 use std::hint::black_box;
 use std::net::IpAddr;
 use std::time::Duration;
@@ -64,6 +64,7 @@ fn criterion_benchmark(c: &mut Criterion) {
         true,
         vec![],
         false,
+        ScanOptions::default(),
     );
 
     c.bench_function("portscan tcp", |b| {
@@ -80,6 +81,7 @@ fn criterion_benchmark(c: &mut Criterion) {
         true,
         vec![],
         true,
+        ScanOptions::default(),
     );
 
     let mut udp_group = c.benchmark_group("portscan udp");
@@ -97,7 +99,7 @@ fn criterion_benchmark(c: &mut Criterion) {
     let mut address_group = c.benchmark_group("address parsing");
     address_group.measurement_time(Duration::from_secs(10));
     address_group.bench_function("parse addresses with exclusions", |b| {
-        b.iter(|| bench_address_parsing())
+        b.iter(bench_address_parsing)
     });
     address_group.finish();
 }
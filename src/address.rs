@@ -1,8 +1,8 @@
 //! Provides functions to parse input IP addresses, CIDRs or files.
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs::{self, File};
 use std::io::{prelude::*, BufReader};
-use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs};
 use std::path::Path;
 use std::str::FromStr;
 
@@ -16,6 +16,9 @@ use log::debug;
 use crate::input::Opts;
 use crate::warning;
 
+/// The most addresses an IP range may hold, as many as a /8 network.
+const MAX_IP_RANGE_SIZE: u128 = 1 << 24;
+
 /// Parses the string(s) into IP addresses.
 ///
 /// Goes through all possible IP inputs (files or via argparsing).
@@ -31,12 +34,43 @@ use crate::warning;
 ///
 /// Finally, any duplicates are removed to avoid excessive scans.
 pub fn parse_addresses(input: &Opts) -> Vec<IpAddr> {
+    parse_addresses_with_hostnames(input).0
+}
+
+/// Parses the string(s) into IP addresses like `parse_addresses`, and also
+/// returns the hostname each IP was resolved from, if any.
+///
+/// A hostname may resolve to several IPs, which are all scanned.
+///
+/// ```rust
+/// # use rustscan::input::Opts;
+/// # use rustscan::address::parse_addresses_with_hostnames;
+/// let mut opts = Opts::default();
+/// opts.addresses = vec!["127.0.0.1".to_owned()];
+///
+/// let (ips, hostnames) = parse_addresses_with_hostnames(&opts);
+/// assert!(hostnames.is_empty());
+/// ```
+pub fn parse_addresses_with_hostnames(input: &Opts) -> (Vec<IpAddr>, BTreeMap<IpAddr, String>) {
+    let backup_resolver = get_resolver(&input.resolver);
+    parse_addresses_with_resolver(input, &backup_resolver, &|hostname| {
+        resolve_hostname(hostname, &backup_resolver)
+    })
+}
+
+/// Parses the targets like `parse_addresses_with_hostnames`, resolving
+/// hostnames with `resolve`.
+fn parse_addresses_with_resolver(
+    input: &Opts,
+    backup_resolver: &Resolver,
+    resolve: &dyn Fn(&str) -> Vec<IpAddr>,
+) -> (Vec<IpAddr>, BTreeMap<IpAddr, String>) {
     let mut ips: Vec<IpAddr> = Vec::new();
+    let mut hostnames: BTreeMap<IpAddr, String> = BTreeMap::new();
     let mut unresolved_addresses: Vec<&str> = Vec::new();
-    let backup_resolver = get_resolver(&input.resolver);
 
     for address in &input.addresses {
-        let parsed_ips = parse_address(address, &backup_resolver);
+        let parsed_ips = parse_target(address, input, resolve, &mut hostnames);
         if !parsed_ips.is_empty() {
             ips.extend(parsed_ips);
         } else {
@@ -45,20 +79,20 @@ pub fn parse_addresses(input: &Opts) -> Vec<IpAddr> {
     }
 
     // If we got to this point this can only be a file path or the wrong input.
-    for file_path in unresolved_addresses {
-        let file_path = Path::new(file_path);
+    for address in unresolved_addresses {
+        let file_path = Path::new(address);
 
         if !file_path.is_file() {
-            warning!(
-                format!("Host {file_path:?} could not be resolved."),
-                input.greppable,
-                input.accessible
-            );
+            let message = match expand_ip_range(address) {
+                Err(e) if is_ip_range(address) => format!("Invalid IP range {address:?}: {e}."),
+                _ => format!("Host {:?} could not be resolved.", Path::new(address)),
+            };
+            warning!(message, input.greppable, input.accessible);
 
             continue;
         }
 
-        if let Ok(x) = read_ips_from_file(file_path, &backup_resolver) {
+        if let Ok(x) = read_ips_from_file(file_path, input, resolve, &mut hostnames) {
             ips.extend(x);
         } else {
             warning!(
@@ -69,16 +103,95 @@ pub fn parse_addresses(input: &Opts) -> Vec<IpAddr> {
         }
     }
 
-    let excluded_cidrs = parse_excluded_networks(&input.exclude_addresses, &backup_resolver);
+    let mut excluded_cidrs: Vec<IpCidr> = Vec::new();
+    for address in input.exclude_addresses.iter().flatten() {
+        let cidrs = parse_single_excluded_address(address, backup_resolver);
+        if cidrs.is_empty() {
+            warning!(
+                format!("Excluded host {address:?} could not be resolved."),
+                input.greppable,
+                input.accessible
+            );
+        }
+        excluded_cidrs.extend(cidrs);
+    }
 
     // Remove duplicated/excluded IPs.
     let mut seen = BTreeSet::new();
     ips.retain(|ip| seen.insert(*ip) && !excluded_cidrs.iter().any(|cidr| cidr.contains(ip)));
+    hostnames.retain(|ip, _| seen.contains(ip));
+
+    (ips, hostnames)
+}
+
+/// Formats an IP for the scan results, along with the hostname it was
+/// resolved from if any, e.g. `example.com (93.184.216.34)`.
+///
+/// ```rust
+/// # use std::collections::BTreeMap;
+/// # use std::net::IpAddr;
+/// # use rustscan::address::host_label;
+/// let ip: IpAddr = "127.0.0.1".parse().unwrap();
+/// assert_eq!(host_label(&ip, &BTreeMap::new()), "127.0.0.1");
+/// ```
+pub fn host_label(ip: &IpAddr, hostnames: &BTreeMap<IpAddr, String>) -> String {
+    match hostnames.get(ip) {
+        Some(hostname) => format!("{hostname} ({ip})"),
+        None => ip.to_string(),
+    }
+}
+
+/// Parses a single target, resolving it with `resolve` when it is a
+/// hostname and remembering which hostname the IPs were resolved from.
+fn parse_target(
+    address: &str,
+    input: &Opts,
+    resolve: &dyn Fn(&str) -> Vec<IpAddr>,
+    hostnames: &mut BTreeMap<IpAddr, String>,
+) -> Vec<IpAddr> {
+    let (ips, hostname) = match parse_literal_address(address) {
+        Some(ips) => (ips, None),
+        None => (resolve(address), Some(address)),
+    };
+    if ips.is_empty() {
+        return ips;
+    }
+
+    let hostname = match split_port_suffix(address) {
+        Some((host, port)) => {
+            warning!(
+                format!("Ignoring port {port} of {address:?}, the ports to scan are set with --ports or --range."),
+                input.greppable,
+                input.accessible
+            );
+            hostname.map(|_| host)
+        }
+        None => hostname,
+    };
+
+    // An IP keeps the first hostname it was resolved from.
+    if let Some(hostname) = hostname {
+        for ip in &ips {
+            hostnames.entry(*ip).or_insert_with(|| hostname.to_owned());
+        }
+    }
 
     ips
 }
 
-/// Given a string, parse it as a host, IP address, or CIDR.
+/// Splits an address written as `host:port` or `[ipv6]:port` into its host
+/// and port.
+fn split_port_suffix(address: &str) -> Option<(&str, u16)> {
+    let (host, port) = address.rsplit_once(':')?;
+    let port = port.parse().ok()?;
+    let is_host = match host.strip_prefix('[') {
+        Some(host) => host.ends_with(']'),
+        None => !host.is_empty() && !host.contains(':'),
+    };
+    is_host.then_some((host, port))
+}
+
+/// Given a string, parse it as a host, IP address, IP range, or CIDR.
 ///
 /// This allows us to pass files as hosts or cidr or IPs easily
 /// Call this every time you have a possible IP-or-host.
@@ -92,44 +205,285 @@ pub fn parse_addresses(input: &Opts) -> Vec<IpAddr> {
 /// let ips = parse_address("127.0.0.1", &Resolver::default().unwrap());
 /// ```
 pub fn parse_address(address: &str, resolver: &Resolver) -> Vec<IpAddr> {
+    parse_literal_address(address).unwrap_or_else(|| resolve_hostname(address, resolver))
+}
+
+/// Parses the addresses that need no DNS lookup: IPs, CIDRs, IP ranges and
+/// aliases. Returns `None` for hostnames.
+fn parse_literal_address(address: &str) -> Option<Vec<IpAddr>> {
     if let Ok(addr) = IpAddr::from_str(address) {
         // `address` is an IP string
-        vec![addr]
+        Some(vec![addr])
+    } else if let Ok(addr) = SocketAddr::from_str(address) {
+        // `address` is an IP string with a port, such as `127.0.0.1:80`
+        Some(vec![addr.ip()])
+    } else if let Some(addr) = parse_bracketed_ipv6(address) {
+        // `address` is a bracketed IPv6 string such as `[::1]`
+        Some(vec![addr])
     } else if let Ok(net_addr) = IpInet::from_str(address) {
         // `address` is a CIDR string
-        net_addr.network().into_iter().addresses().collect()
+        Some(net_addr.network().into_iter().addresses().collect())
+    } else if let Ok(ips) = expand_ip_range(address) {
+        // `address` is an IP range string
+        Some(ips)
+    } else {
+        // `address` is one of the aliases for this machine or its gateway,
+        // or `None` for a hostname
+        resolve_alias(address)
+    }
+}
+
+/// Resolves a hostname or DNS name, with the system resolver first and then
+/// with the dedicated resolver.
+fn resolve_hostname(address: &str, resolver: &Resolver) -> Vec<IpAddr> {
+    match format!("{address}:80").to_socket_addrs() {
+        Ok(addrs) => unique_ips(addrs),
+        // default lookup didn't work, so try again with the dedicated resolver
+        Err(_) => resolve_ips_from_host(address, resolver),
+    }
+}
+
+/// Parses an IPv6 address written in its bracketed form, optionally followed
+/// by a port, e.g. `[2001:db8::1]` or `[2001:db8::1]:443`.
+///
+/// Ports are selected with `--ports` or `--range`, so a port suffix is only
+/// validated here. `parse_addresses` warns that it is ignored.
+fn parse_bracketed_ipv6(address: &str) -> Option<IpAddr> {
+    let (host, suffix) = address.strip_prefix('[')?.split_once(']')?;
+
+    if !suffix.is_empty() {
+        suffix.strip_prefix(':')?.parse::<u16>().ok()?;
+    }
+
+    Ipv6Addr::from_str(host).ok().map(IpAddr::V6)
+}
+
+/// Resolves the target aliases for commonly scanned machines:
+/// - `localhost`, `local` and `me` are this machine's loopback addresses, as
+///   the system resolves `localhost`. 127.0.0.1 is used if that fails.
+/// - `gateway` is the IPv4 default gateway. It is read from the routing
+///   table, which is only supported on Linux.
+///
+/// The aliases take precedence over DNS names of the same name. Returns
+/// `None` when the address is not an alias, and an empty list when the alias
+/// could not be resolved.
+fn resolve_alias(address: &str) -> Option<Vec<IpAddr>> {
+    match address.to_lowercase().as_str() {
+        "localhost" | "local" | "me" => Some(loopback_addresses()),
+        "gateway" => Some(default_gateway().map(IpAddr::V4).into_iter().collect()),
+        _ => None,
+    }
+}
+
+/// This machine's loopback addresses, only including ::1 when the system
+/// resolves `localhost` to it, so IPv4-only setups don't scan it.
+fn loopback_addresses() -> Vec<IpAddr> {
+    let ips: Vec<IpAddr> = match "localhost:0".to_socket_addrs() {
+        Ok(addrs) => unique_ips(addrs)
+            .into_iter()
+            .filter(IpAddr::is_loopback)
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+
+    if ips.is_empty() {
+        vec![IpAddr::V4(Ipv4Addr::LOCALHOST)]
     } else {
-        // `address` is a hostname or DNS name
-        // attempt default DNS lookup
-        match format!("{address}:80").to_socket_addrs() {
-            Ok(mut iter) => vec![iter.next().unwrap().ip()],
-            // default lookup didn't work, so try again with the dedicated resolver
-            Err(_) => resolve_ips_from_host(address, resolver),
+        ips
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn default_gateway() -> Option<Ipv4Addr> {
+    let route_table = fs::read_to_string("/proc/net/route").ok()?;
+    parse_default_gateway(&route_table)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn default_gateway() -> Option<Ipv4Addr> {
+    debug!("Resolving the gateway alias is only supported on Linux");
+    None
+}
+
+/// Route flag set when the route goes through a gateway, see `route(8)`.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+const RTF_GATEWAY: u32 = 0x2;
+
+/// Finds the gateway of the default route in a `/proc/net/route` table.
+///
+/// Addresses and flags in the table are hex encoded, addresses in the host's
+/// byte order. The default route has both its destination and mask set to
+/// 0.0.0.0. Default routes bound to an interface only, e.g. a WireGuard or
+/// PPP link, have no gateway and are skipped.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_default_gateway(route_table: &str) -> Option<Ipv4Addr> {
+    route_table.lines().skip(1).find_map(|line| {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let [_, "00000000", gateway, flags, _, _, _, "00000000", ..] = fields.as_slice() else {
+            return None;
+        };
+        let gateway = u32::from_str_radix(gateway, 16).ok()?;
+        let flags = u32::from_str_radix(flags, 16).ok()?;
+        if flags & RTF_GATEWAY == 0 || gateway == 0 {
+            return None;
+        }
+        Some(Ipv4Addr::from(gateway.to_ne_bytes()))
+    })
+}
+
+/// Returns true when the string starts with an IP address followed by a
+/// dash, i.e. it is most likely meant as an IP range. Hostnames such as
+/// `10.0.0.1-web.example.com` look the same, so a range that does not parse
+/// is resolved through DNS instead.
+fn is_ip_range(address: &str) -> bool {
+    address
+        .split_once('-')
+        .is_some_and(|(start, _)| IpAddr::from_str(start).is_ok())
+}
+
+/// Expands an IP range into every address it contains, both ends included.
+///
+/// Two forms are accepted:
+/// 1. Full, with both ends written out (e.g. "192.168.0.10-192.168.0.50")
+/// 2. Shorthand, where only the last octet of the end is given (e.g.
+///    "192.168.0.10-50"). For IPv6 the last segment is given in hex
+///    (e.g. "2001:db8::10-1f").
+///
+/// Ranges of more than `MAX_IP_RANGE_SIZE` addresses are rejected, a CIDR
+/// is better suited for those.
+///
+/// ```rust
+/// # use rustscan::address::expand_ip_range;
+/// let ips = expand_ip_range("192.168.0.10-50").unwrap();
+/// assert_eq!(ips.len(), 41);
+/// ```
+pub fn expand_ip_range(token: &str) -> Result<Vec<IpAddr>, String> {
+    let (start, end) = parse_ip_range(token)?;
+    let (first, last) = range_bounds(start, end);
+
+    if last - first >= MAX_IP_RANGE_SIZE {
+        return Err(format!(
+            "the range holds more than {MAX_IP_RANGE_SIZE} addresses, use a CIDR instead"
+        ));
+    }
+
+    Ok((first..=last)
+        .map(|ip| match start {
+            IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::from(ip as u32)),
+            IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::from(ip)),
+        })
+        .collect())
+}
+
+/// Parses the two ends of an IP range, see `expand_ip_range` for the formats.
+fn parse_ip_range(token: &str) -> Result<(IpAddr, IpAddr), String> {
+    let Some((start, end)) = token.split_once('-') else {
+        return Err(String::from(
+            "the range format must be 'start-end'. Example: 192.168.0.1-192.168.0.10.",
+        ));
+    };
+    let start = IpAddr::from_str(start).map_err(|e| format!("invalid range start: {e}"))?;
+
+    let end = match (IpAddr::from_str(end), start) {
+        (Ok(end), _) => end,
+        (Err(_), IpAddr::V4(start)) => {
+            let last = u8::from_str(end).map_err(|e| format!("invalid range end: {e}"))?;
+            let [a, b, c, _] = start.octets();
+            IpAddr::V4(Ipv4Addr::new(a, b, c, last))
+        }
+        (Err(_), IpAddr::V6(start)) => {
+            let last =
+                u16::from_str_radix(end, 16).map_err(|e| format!("invalid range end: {e}"))?;
+            let mut segments = start.segments();
+            segments[7] = last;
+            IpAddr::V6(Ipv6Addr::from(segments))
+        }
+    };
+
+    if start > end {
+        return Err(format!(
+            "range start {start} is greater than range end {end}"
+        ));
+    }
+
+    if start.is_ipv4() != end.is_ipv4() {
+        return Err(String::from(
+            "range start and end must be of the same IP version",
+        ));
+    }
+
+    Ok((start, end))
+}
+
+/// Converts the ends of an IP range of a single IP version to integers.
+fn range_bounds(start: IpAddr, end: IpAddr) -> (u128, u128) {
+    let to_int = |ip| match ip {
+        IpAddr::V4(ip) => u128::from(u32::from(ip)),
+        IpAddr::V6(ip) => u128::from(ip),
+    };
+    (to_int(start), to_int(end))
+}
+
+/// Covers an IP range with the fewest CIDRs, so excluding a range costs at
+/// most a few CIDR checks per address instead of one per excluded address.
+fn range_to_cidrs(start: IpAddr, end: IpAddr) -> Vec<IpCidr> {
+    let (mut first, last) = range_bounds(start, end);
+    let bits = if start.is_ipv4() { 32 } else { 128 };
+    // Mask of the host bits of a network with `host_bits` of them.
+    let host_mask = |host_bits: u32| u128::MAX.checked_shr(128 - host_bits).unwrap_or(0);
+
+    let mut cidrs = Vec::new();
+    loop {
+        // The largest network starting at `first` that does not go past `last`.
+        let mut host_bits = first.trailing_zeros().min(bits);
+        while host_mask(host_bits) > last - first {
+            host_bits -= 1;
         }
+
+        let network = match start {
+            IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::from(first as u32)),
+            IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::from(first)),
+        };
+        cidrs.push(IpCidr::new(network, (bits - host_bits) as u8).unwrap());
+
+        if first + host_mask(host_bits) == last {
+            return cidrs;
+        }
+        first += host_mask(host_bits) + 1;
     }
 }
 
 /// Uses DNS to get the IPS associated with host
 fn resolve_ips_from_host(source: &str, backup_resolver: &Resolver) -> Vec<IpAddr> {
-    let mut ips: Vec<IpAddr> = Vec::new();
-
     if let Ok(addrs) = source.to_socket_addrs() {
-        for ip in addrs {
-            ips.push(ip.ip());
-        }
+        unique_ips(addrs)
     } else if let Ok(addrs) = backup_resolver.lookup_ip(source) {
-        ips.extend(addrs.iter());
+        addrs.iter().collect()
+    } else {
+        Vec::new()
     }
+}
 
-    ips
+/// Collects every IP a host resolved to, in resolution order.
+///
+/// The system resolver returns one socket address per record and socket
+/// type, so the same IP may appear several times.
+fn unique_ips(addrs: impl Iterator<Item = SocketAddr>) -> Vec<IpAddr> {
+    let mut seen = BTreeSet::new();
+    addrs
+        .map(|addr| addr.ip())
+        .filter(|ip| seen.insert(*ip))
+        .collect()
 }
 
 /// Parses excluded networks from a list of addresses.
 ///
-/// This function handles three types of inputs:
+/// This function handles five types of inputs:
 /// 1. CIDR notation (e.g. "192.168.0.0/24")
 /// 2. Single IP addresses (e.g. "192.168.0.1")
-/// 3. Hostnames that need to be resolved (e.g. "example.com")
+/// 3. Aliases for this machine or its gateway (e.g. "me" or "gateway")
+/// 4. IP ranges (e.g. "192.168.0.10-50")
+/// 5. Hostnames that need to be resolved (e.g. "example.com")
 ///
 /// ```rust
 /// # use rustscan::address::parse_excluded_networks;
@@ -148,16 +502,27 @@ pub fn parse_excluded_networks(
         .collect()
 }
 
-/// Parses a single address into an IpCidr, handling CIDR notation, IP addresses, and hostnames.
+/// Parses a single address into an IpCidr, handling CIDR notation, IP addresses, aliases, IP ranges, and hostnames.
 fn parse_single_excluded_address(addr: &str, resolver: &Resolver) -> Vec<IpCidr> {
     if let Ok(cidr) = IpCidr::from_str(addr) {
         return vec![cidr];
     }
 
-    if let Ok(ip) = IpAddr::from_str(addr) {
+    if let Some(ip) = IpAddr::from_str(addr)
+        .ok()
+        .or_else(|| parse_bracketed_ipv6(addr))
+    {
         return vec![IpCidr::new_host(ip)];
     }
 
+    if let Some(ips) = resolve_alias(addr) {
+        return ips.into_iter().map(IpCidr::new_host).collect();
+    }
+
+    if let Ok((start, end)) = parse_ip_range(addr) {
+        return range_to_cidrs(start, end);
+    }
+
     resolve_ips_from_host(addr, resolver)
         .into_iter()
         .map(IpCidr::new_host)
@@ -216,7 +581,9 @@ fn read_resolver_from_file(path: &str) -> Result<Vec<IpAddr>, std::io::Error> {
 /// Parses an input file of IPs and uses those
 fn read_ips_from_file(
     ips: &std::path::Path,
-    backup_resolver: &Resolver,
+    input: &Opts,
+    resolve: &dyn Fn(&str) -> Vec<IpAddr>,
+    hostnames: &mut BTreeMap<IpAddr, String>,
 ) -> Result<Vec<IpAddr>, std::io::Error> {
     let file = File::open(ips)?;
     let reader = BufReader::new(file);
@@ -225,7 +592,7 @@ fn read_ips_from_file(
 
     for address_line in reader.lines() {
         if let Ok(address) = address_line {
-            ips.extend(parse_address(&address, backup_resolver));
+            ips.extend(parse_target(&address, input, resolve, hostnames));
         } else {
             debug!("Line in file is not valid");
         }
@@ -236,8 +603,16 @@ fn read_ips_from_file(
 
 #[cfg(test)]
 mod tests {
-    use super::{get_resolver, parse_addresses, Opts};
-    use std::net::Ipv4Addr;
+    use super::{
+        expand_ip_range, get_resolver, host_label, parse_addresses, parse_addresses_with_resolver,
+        parse_bracketed_ipv6, parse_default_gateway, range_to_cidrs, resolve_alias,
+        split_port_suffix, unique_ips, Opts,
+    };
+    use cidr_utils::cidr::IpCidr;
+    use clap::Parser;
+    use std::net::SocketAddr;
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+    use std::str::FromStr;
 
     #[test]
     fn parse_correct_addresses() {
@@ -318,6 +693,268 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_bracketed_ipv6_addresses() {
+        assert_eq!(
+            parse_bracketed_ipv6("[::1]"),
+            Some(IpAddr::V6(Ipv6Addr::LOCALHOST))
+        );
+        assert_eq!(
+            parse_bracketed_ipv6("[::1]:80"),
+            Some(IpAddr::V6(Ipv6Addr::LOCALHOST))
+        );
+        assert_eq!(parse_bracketed_ipv6("[::1]:"), None);
+        assert_eq!(parse_bracketed_ipv6("[::1]:99999"), None);
+        assert_eq!(parse_bracketed_ipv6("[127.0.0.1]"), None);
+        assert_eq!(parse_bracketed_ipv6("::1"), None);
+    }
+
+    #[test]
+    fn parse_bracketed_ipv6_list() {
+        let opts = Opts::parse_from(["rustscan", "-a", "[::1],[2001:db8::1]:443,2001:db8::2"]);
+
+        let ips = parse_addresses(&opts);
+
+        assert_eq!(
+            ips,
+            [
+                Ipv6Addr::LOCALHOST,
+                Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1),
+                Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_addresses_with_port_suffix() {
+        let opts = Opts {
+            addresses: vec!["[::1]:443".to_owned(), "127.0.0.1:443".to_owned()],
+            ..Default::default()
+        };
+
+        let ips = parse_addresses(&opts);
+
+        assert_eq!(
+            ips,
+            [
+                IpAddr::V6(Ipv6Addr::LOCALHOST),
+                IpAddr::V4(Ipv4Addr::LOCALHOST)
+            ]
+        );
+    }
+
+    #[test]
+    fn find_port_suffixes() {
+        assert_eq!(split_port_suffix("127.0.0.1:443"), Some(("127.0.0.1", 443)));
+        assert_eq!(split_port_suffix("[::1]:443"), Some(("[::1]", 443)));
+        assert_eq!(
+            split_port_suffix("example.com:8080"),
+            Some(("example.com", 8080))
+        );
+        assert_eq!(split_port_suffix("::1"), None);
+        assert_eq!(split_port_suffix("2001:db8::80"), None);
+        assert_eq!(split_port_suffix("example.com"), None);
+    }
+
+    #[test]
+    fn parse_addresses_with_bracketed_ipv6_exclusions() {
+        let opts = Opts {
+            addresses: vec!["::1".to_owned(), "2001:db8::1".to_owned()],
+            exclude_addresses: Some(vec!["[::1]".to_owned()]),
+            ..Default::default()
+        };
+
+        let ips = parse_addresses(&opts);
+
+        assert_eq!(ips, [Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1)]);
+    }
+
+    #[test]
+    fn expand_full_ip_range() {
+        let ips = expand_ip_range("192.168.0.254-192.168.1.1").unwrap();
+
+        assert_eq!(
+            ips,
+            [
+                Ipv4Addr::new(192, 168, 0, 254),
+                Ipv4Addr::new(192, 168, 0, 255),
+                Ipv4Addr::new(192, 168, 1, 0),
+                Ipv4Addr::new(192, 168, 1, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn expand_shorthand_ip_range() {
+        let ips = expand_ip_range("192.168.0.10-12").unwrap();
+
+        assert_eq!(
+            ips,
+            [
+                Ipv4Addr::new(192, 168, 0, 10),
+                Ipv4Addr::new(192, 168, 0, 11),
+                Ipv4Addr::new(192, 168, 0, 12),
+            ]
+        );
+
+        let ips = expand_ip_range("2001:db8::fe-ff").unwrap();
+
+        assert_eq!(
+            ips,
+            [
+                Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0xfe),
+                Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0xff),
+            ]
+        );
+    }
+
+    #[test]
+    fn expand_invalid_ip_ranges() {
+        assert!(expand_ip_range("192.168.0.50-10").is_err());
+        assert!(expand_ip_range("192.168.0.50-192.168.0.10").is_err());
+        assert!(expand_ip_range("192.168.0.10-256").is_err());
+        assert!(expand_ip_range("192.168.0.10-::1").is_err());
+        assert!(expand_ip_range("192.168.0.10").is_err());
+        assert!(expand_ip_range("2001:db8::-2001:db8::ffff:ffff").is_err());
+        assert!(expand_ip_range("10.0.0.0-11.0.0.0").is_err());
+        assert_eq!(
+            expand_ip_range("10.0.0.0-10.255.255.255").unwrap().len(),
+            1 << 24
+        );
+    }
+
+    #[test]
+    fn cover_ip_range_with_cidrs() {
+        let cidrs = range_to_cidrs(
+            IpAddr::V4(Ipv4Addr::new(192, 168, 0, 1)),
+            IpAddr::V4(Ipv4Addr::new(192, 168, 0, 6)),
+        );
+        assert_eq!(
+            cidrs,
+            [
+                "192.168.0.1/32",
+                "192.168.0.2/31",
+                "192.168.0.4/31",
+                "192.168.0.6/32"
+            ]
+            .map(|cidr| IpCidr::from_str(cidr).unwrap())
+        );
+
+        let cidrs = range_to_cidrs(
+            IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0)),
+            IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0xffff, 0xffff)),
+        );
+        assert_eq!(cidrs, [IpCidr::from_str("2001:db8::/96").unwrap()]);
+
+        let cidrs = range_to_cidrs(
+            IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+            IpAddr::V4(Ipv4Addr::BROADCAST),
+        );
+        assert_eq!(cidrs, [IpCidr::from_str("0.0.0.0/0").unwrap()]);
+    }
+
+    #[test]
+    fn parse_ip_range_addresses() {
+        let opts = Opts {
+            addresses: vec!["192.168.0.1-3".to_owned(), "192.168.0.5-1".to_owned()],
+            ..Default::default()
+        };
+
+        let ips = parse_addresses(&opts);
+
+        assert_eq!(
+            ips,
+            [
+                Ipv4Addr::new(192, 168, 0, 1),
+                Ipv4Addr::new(192, 168, 0, 2),
+                Ipv4Addr::new(192, 168, 0, 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_addresses_with_ip_range_exclusions() {
+        let opts = Opts {
+            addresses: vec!["192.168.0.1-5".to_owned()],
+            exclude_addresses: Some(vec!["192.168.0.2-3".to_owned(), "192.168.0.5-1".to_owned()]),
+            ..Default::default()
+        };
+
+        let ips = parse_addresses(&opts);
+
+        assert_eq!(
+            ips,
+            [
+                Ipv4Addr::new(192, 168, 0, 1),
+                Ipv4Addr::new(192, 168, 0, 4),
+                Ipv4Addr::new(192, 168, 0, 5),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_addresses_with_large_ip_range_exclusions() {
+        let opts = Opts {
+            addresses: vec!["10.0.0.0/17".to_owned()],
+            exclude_addresses: Some(vec!["10.0.0.0-10.0.127.254".to_owned()]),
+            ..Default::default()
+        };
+
+        let ips = parse_addresses(&opts);
+
+        assert_eq!(ips, [Ipv4Addr::new(10, 0, 127, 255)]);
+    }
+
+    #[test]
+    fn resolve_local_aliases() {
+        let ips = resolve_alias("localhost").unwrap();
+
+        assert!(!ips.is_empty());
+        assert!(ips.iter().all(IpAddr::is_loopback));
+        for alias in ["me", "LOCAL"] {
+            assert_eq!(resolve_alias(alias), Some(ips.clone()));
+        }
+        assert_eq!(resolve_alias("example.com"), None);
+    }
+
+    #[test]
+    fn parse_addresses_with_alias_exclusions() {
+        let opts = Opts {
+            addresses: vec!["127.0.0.1".to_owned(), "192.168.0.1".to_owned()],
+            exclude_addresses: Some(vec!["me".to_owned()]),
+            ..Default::default()
+        };
+
+        let ips = parse_addresses(&opts);
+
+        assert_eq!(ips, [Ipv4Addr::new(192, 168, 0, 1)]);
+    }
+
+    #[test]
+    fn parse_gateway_from_route_table() {
+        let gateway = u32::from_ne_bytes([192, 168, 1, 254]);
+        let route_table = format!(
+            "Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\t\tMTU\tWindow\tIRTT\n\
+             eth0\t0001A8C0\t00000000\t0001\t0\t0\t100\t00FFFFFF\t0\t0\t0\n\
+             wg0\t00000000\t00000000\t0001\t0\t0\t0\t00000000\t0\t0\t0\n\
+             tun0\t00000000\t0108080A\t0003\t0\t0\t0\t00000080\t0\t0\t0\n\
+             eth0\t00000000\t{gateway:08X}\t0003\t0\t0\t100\t00000000\t0\t0\t0\n"
+        );
+
+        assert_eq!(
+            parse_default_gateway(&route_table),
+            Some(Ipv4Addr::new(192, 168, 1, 254))
+        );
+        assert_eq!(
+            parse_default_gateway(
+                "Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\n\
+                 wg0\t00000000\t00000000\t0001\t0\t0\t0\t00000000\n"
+            ),
+            None
+        );
+        assert_eq!(parse_default_gateway("Iface\tDestination\tGateway\n"), None);
+    }
+
     #[test]
     fn parse_correct_host_addresses() {
         let opts = Opts {
@@ -327,7 +964,82 @@ mod tests {
 
         let ips = parse_addresses(&opts);
 
-        assert_eq!(ips.len(), 1);
+        assert!(!ips.is_empty());
+    }
+
+    #[test]
+    fn keep_every_resolved_ip() {
+        let resolved = [
+            "93.184.216.34:80",
+            "[2606:2800:220:1::]:80",
+            "93.184.216.34:80",
+            "93.184.216.35:80",
+        ]
+        .map(|addr| addr.parse::<SocketAddr>().unwrap());
+
+        let ips = unique_ips(resolved.iter().copied());
+
+        assert_eq!(
+            ips,
+            [
+                IpAddr::V4(Ipv4Addr::new(93, 184, 216, 34)),
+                IpAddr::V6(Ipv6Addr::new(0x2606, 0x2800, 0x220, 1, 0, 0, 0, 0)),
+                IpAddr::V4(Ipv4Addr::new(93, 184, 216, 35)),
+            ]
+        );
+    }
+
+    #[test]
+    fn label_ips_with_their_hostname() {
+        let example_ips = [
+            IpAddr::V4(Ipv4Addr::new(93, 184, 216, 34)),
+            IpAddr::V6(Ipv6Addr::new(0x2606, 0x2800, 0x220, 1, 0, 0, 0, 0)),
+        ];
+        let www_ip = IpAddr::V4(Ipv4Addr::new(93, 184, 216, 35));
+        let other_ip = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        // A resolver returning two records for example.com.
+        let resolve = |hostname: &str| match hostname {
+            "example.com" => example_ips.to_vec(),
+            "www.example.com:8080" => vec![www_ip, example_ips[0]],
+            _ => Vec::new(),
+        };
+        let opts = Opts {
+            addresses: vec![
+                "example.com".to_owned(),
+                "www.example.com:8080".to_owned(),
+                "10.0.0.1:443".to_owned(),
+                "10.0.0.0/31".to_owned(),
+                "localhost".to_owned(),
+            ],
+            ..Default::default()
+        };
+
+        let (ips, hostnames) = parse_addresses_with_resolver(&opts, &get_resolver(&None), &resolve);
+
+        assert_eq!(
+            ips[..5],
+            [
+                example_ips[0],
+                example_ips[1],
+                www_ip,
+                other_ip,
+                IpAddr::V4(Ipv4Addr::new(10, 0, 0, 0)),
+            ]
+        );
+        assert_eq!(
+            host_label(&example_ips[0], &hostnames),
+            "example.com (93.184.216.34)"
+        );
+        assert_eq!(
+            host_label(&example_ips[1], &hostnames),
+            "example.com (2606:2800:220:1::)"
+        );
+        assert_eq!(
+            host_label(&www_ip, &hostnames),
+            "www.example.com (93.184.216.35)"
+        );
+        assert_eq!(host_label(&other_ip, &hostnames), "10.0.0.1");
+        assert_eq!(hostnames.len(), 3);
     }
 
     #[test]
@@ -364,7 +1076,8 @@ mod tests {
 
         let ips = parse_addresses(&opts);
 
-        assert_eq!(ips.len(), 3);
+        // google.com and example.com may each resolve to several IPs
+        assert!(ips.len() >= 3);
     }
 
     #[test]
@@ -2,6 +2,7 @@
 use clap::{Parser, ValueEnum};
 use serde_derive::Deserialize;
 use std::fs;
+use std::net::SocketAddr;
 use std::path::PathBuf;
 
 const LOWEST_PORT_NUMBER: u16 = 1;
@@ -72,7 +73,9 @@ fn parse_range(input: &str) -> Result<PortRange, String> {
 /// - Discord  <http://discord.skerritt.blog>
 /// - GitHub <https://github.com/RustScan/RustScan>
 pub struct Opts {
-    /// A comma-delimited list or newline-delimited file of separated CIDRs, IPs, or hosts to be scanned.
+    /// A comma-delimited list or newline-delimited file of separated CIDRs, IPs, IP ranges, or hosts to be scanned.
+    /// The aliases localhost, local and me scan this machine, and gateway scans the default gateway (Linux only).
+    /// The aliases are used even if a DNS name of the same name exists.
     #[arg(short, long, value_delimiter = ',')]
     pub addresses: Vec<String>,
 
@@ -113,7 +116,7 @@ pub struct Opts {
     /// it will do every port at the same time. Although, your OS may not
     /// support this.
     #[arg(short, long, default_value = "4500")]
-    pub batch_size: u16,
+    pub batch_size: u32,
 
     /// The timeout in milliseconds before a port is assumed to be closed.
     #[arg(short, long, default_value = "1500")]
@@ -154,13 +157,52 @@ pub struct Opts {
     #[arg(short, long, value_delimiter = ',')]
     pub exclude_ports: Option<Vec<u16>>,
 
-    /// A list of comma separated CIDRs, IPs, or hosts to be excluded from scanning.
+    /// A list of comma separated CIDRs, IPs, IP ranges, or hosts to be excluded from scanning.
+    /// The same aliases as --addresses are accepted.
     #[arg(short = 'x', long = "exclude-addresses", value_delimiter = ',')]
     pub exclude_addresses: Option<Vec<String>>,
 
     /// UDP scanning mode, finds UDP ports that send back responses
     #[arg(long)]
     pub udp: bool,
+
+    /// The maximum number of hosts to scan at the same time. Hosts are scanned
+    /// in fixed groups of this size, the next group starting once every host
+    /// of the current one is done, so a slow host holds back its group. By
+    /// default every host is scanned at once. If set to 0, rustscan will
+    /// correct it to 1.
+    #[arg(long)]
+    pub max_concurrent_hosts: Option<usize>,
+
+    /// Stop scanning a host as soon as one open port is found on it. Useful to
+    /// quickly check which hosts are exposed at all. Combine it with --top so
+    /// the most common ports are tried first.
+    #[arg(long)]
+    pub first_port_only: bool,
+
+    /// Start with a small batch size and double it after each completed batch
+    /// until --batch-size is reached, instead of opening every socket at once.
+    /// Gentler on rate limiters and fragile networks.
+    #[arg(long)]
+    pub ramp_up: bool,
+
+    /// Seed for the random scan order. Scans using the same seed try the ports
+    /// in the same order, which makes them reproducible. Requires
+    /// --scan-order random.
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Address of a SOCKS5 proxy to scan through, e.g. 127.0.0.1:1080.
+    /// Open ports are then the ones the proxy could reach. Not available
+    /// for UDP scans, and scripts such as nmap still connect directly.
+    #[arg(long, conflicts_with = "udp")]
+    pub proxy: Option<SocketAddr>,
+
+    /// Append each open port to this file as a JSON line while scanning,
+    /// e.g. {"ip":"127.0.0.1","port":80,"protocol":"tcp"}. Ports found before
+    /// an interrupted scan are kept.
+    #[arg(long, value_name = "FILE")]
+    pub jsonl: Option<PathBuf>,
 }
 
 #[cfg(not(tarpaulin_include))]
@@ -187,6 +229,18 @@ impl Opts {
         }
     }
 
+    /// Whether a SOCKS5 proxy is set for a UDP scan, which cannot go through
+    /// it. clap rejects `--udp` with `--proxy`, but either may also come from
+    /// the configuration file.
+    pub fn udp_with_proxy(&self) -> bool {
+        self.udp && self.proxy.is_some()
+    }
+
+    /// Whether a seed is set for a serial scan order, where it has no effect.
+    pub fn seed_without_random_order(&self) -> bool {
+        self.seed.is_some() && self.scan_order != ScanOrder::Random
+    }
+
     fn merge_required(&mut self, config: &Config) {
         macro_rules! merge_required {
             ($($field: ident),+) => {
@@ -199,8 +253,18 @@ impl Opts {
         }
 
         merge_required!(
-            addresses, greppable, accessible, batch_size, timeout, tries, scan_order, scripts,
-            command, udp
+            addresses,
+            greppable,
+            accessible,
+            batch_size,
+            timeout,
+            tries,
+            scan_order,
+            scripts,
+            command,
+            udp,
+            first_port_only,
+            ramp_up
         );
     }
 
@@ -220,7 +284,17 @@ impl Opts {
             self.ports = config.ports.clone();
         }
 
-        merge_optional!(range, resolver, ulimit, exclude_ports, exclude_addresses);
+        merge_optional!(
+            range,
+            resolver,
+            ulimit,
+            exclude_ports,
+            exclude_addresses,
+            max_concurrent_hosts,
+            seed,
+            proxy,
+            jsonl
+        );
     }
 }
 
@@ -247,6 +321,12 @@ impl Default for Opts {
             exclude_ports: None,
             exclude_addresses: None,
             udp: false,
+            max_concurrent_hosts: None,
+            first_port_only: false,
+            ramp_up: false,
+            seed: None,
+            proxy: None,
+            jsonl: None,
         }
     }
 }
@@ -262,7 +342,7 @@ pub struct Config {
     range: Option<PortRange>,
     greppable: Option<bool>,
     accessible: Option<bool>,
-    batch_size: Option<u16>,
+    batch_size: Option<u32>,
     timeout: Option<u32>,
     tries: Option<u8>,
     ulimit: Option<u64>,
@@ -273,6 +353,12 @@ pub struct Config {
     exclude_ports: Option<Vec<u16>>,
     exclude_addresses: Option<Vec<String>>,
     udp: Option<bool>,
+    max_concurrent_hosts: Option<usize>,
+    first_port_only: Option<bool>,
+    ramp_up: Option<bool>,
+    seed: Option<u64>,
+    proxy: Option<SocketAddr>,
+    jsonl: Option<PathBuf>,
 }
 
 #[cfg(not(tarpaulin_include))]
@@ -348,6 +434,12 @@ mod tests {
                 exclude_ports: None,
                 exclude_addresses: None,
                 udp: Some(false),
+                max_concurrent_hosts: None,
+                first_port_only: Some(true),
+                ramp_up: Some(true),
+                seed: None,
+                proxy: None,
+                jsonl: None,
             }
         }
     }
@@ -377,6 +469,13 @@ mod tests {
         assert_eq!(command, opts.command);
     }
 
+    #[test]
+    fn parse_batch_size_above_u16() {
+        let opts = Opts::parse_from(["rustscan", "-a", "127.0.0.1", "-b", "100000"]);
+
+        assert_eq!(opts.batch_size, 100_000);
+    }
+
     #[test]
     fn opts_no_merge_when_config_is_ignored() {
         let mut opts = Opts::default();
@@ -406,6 +505,8 @@ mod tests {
         assert_eq!(opts.accessible, config.accessible.unwrap());
         assert_eq!(opts.scan_order, config.scan_order.unwrap());
         assert_eq!(opts.scripts, ScriptsRequired::Default);
+        assert_eq!(opts.first_port_only, config.first_port_only.unwrap());
+        assert_eq!(opts.ramp_up, config.ramp_up.unwrap());
     }
 
     #[test]
@@ -418,11 +519,45 @@ mod tests {
         });
         config.ulimit = Some(1_000);
         config.resolver = Some("1.1.1.1".to_owned());
+        config.max_concurrent_hosts = Some(4);
+        config.seed = Some(42);
+        config.proxy = Some("127.0.0.1:1080".parse().unwrap());
 
         opts.merge_optional(&config);
 
         assert_eq!(opts.range, config.range);
         assert_eq!(opts.ulimit, config.ulimit);
         assert_eq!(opts.resolver, config.resolver);
+        assert_eq!(opts.max_concurrent_hosts, config.max_concurrent_hosts);
+        assert_eq!(opts.seed, config.seed);
+        assert_eq!(opts.proxy, config.proxy);
+    }
+
+    #[test]
+    fn opts_seed_requires_random_order() {
+        let opts = Opts::parse_from(["rustscan", "-a", "127.0.0.1", "--seed", "42"]);
+
+        assert!(opts.seed_without_random_order());
+
+        let mut opts = Opts::parse_from(["rustscan", "-a", "127.0.0.1", "--seed", "42"]);
+        let config = Config::default();
+
+        opts.merge(&config);
+
+        assert_eq!(opts.scan_order, ScanOrder::Random);
+        assert!(!opts.seed_without_random_order());
+    }
+
+    #[test]
+    fn opts_merge_udp_with_proxy() {
+        let mut opts = Opts::parse_from(["rustscan", "-a", "127.0.0.1", "--proxy", "127.0.0.1:9"]);
+        let mut config = Config::default();
+        config.udp = Some(true);
+
+        assert!(!opts.udp_with_proxy());
+
+        opts.merge(&config);
+
+        assert!(opts.udp_with_proxy());
     }
 }
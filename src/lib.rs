@@ -13,7 +13,7 @@
 //!
 //! use rustscan::input::{PortRange, ScanOrder};
 //! use rustscan::port_strategy::PortStrategy;
-//! use rustscan::scanner::Scanner;
+//! use rustscan::scanner::{ScanOptions, Scanner};
 //!
 //! fn main() {
 //!     let addrs = vec!["127.0.0.1".parse::<IpAddr>().unwrap()];
@@ -32,6 +32,7 @@
 //!         true, // accessible, should the output be A11Y compliant?
 //!         vec![9000], // What ports should RustScan exclude?
 //!         false, // is this a UDP scan?
+//!         ScanOptions::default(), // optional behaviours such as a SOCKS5 proxy or JSON lines output, all off by default
 //!     );
 //!
 //!     let scan_result = block_on(scanner.run());
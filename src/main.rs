@@ -5,18 +5,18 @@
 use rustscan::benchmark::{Benchmark, NamedTimer};
 use rustscan::input::{self, Config, Opts, ScriptsRequired};
 use rustscan::port_strategy::PortStrategy;
-use rustscan::scanner::Scanner;
+use rustscan::scanner::{socks5, ScanOptions, Scanner};
 use rustscan::scripts::{init_scripts, Script, ScriptFile};
 use rustscan::{detail, funny_opening, output, warning};
 
 use colorful::{Color, Colorful};
 use futures::executor::block_on;
 use std::collections::HashMap;
-use std::net::IpAddr;
+use std::fs::OpenOptions;
 use std::string::ToString;
 use std::time::Duration;
 
-use rustscan::address::parse_addresses;
+use rustscan::address::{host_label, parse_addresses_with_hostnames};
 
 extern crate colorful;
 extern crate dirs;
@@ -25,7 +25,7 @@ extern crate dirs;
 #[cfg(unix)]
 const DEFAULT_FILE_DESCRIPTORS_LIMIT: u64 = 8000;
 // Safest batch size based on experimentation
-const AVERAGE_BATCH_SIZE: u16 = 3000;
+const AVERAGE_BATCH_SIZE: u32 = 3000;
 
 #[macro_use]
 extern crate log;
@@ -48,6 +48,24 @@ fn main() {
 
     debug!("Main() `opts` arguments are {opts:?}");
 
+    if opts.udp_with_proxy() {
+        warning!(
+            "UDP scans cannot go through a SOCKS5 proxy, remove either udp or proxy from the arguments or the config file.",
+            opts.greppable,
+            opts.accessible
+        );
+        std::process::exit(1);
+    }
+
+    if opts.seed_without_random_order() {
+        warning!(
+            "A seed only applies to a random scan order, use --scan-order random or remove seed from the arguments or the config file.",
+            opts.greppable,
+            opts.accessible
+        );
+        std::process::exit(1);
+    }
+
     let scripts_to_run: Vec<ScriptFile> = match init_scripts(&opts.scripts) {
         Ok(scripts_to_run) => scripts_to_run,
         Err(e) => {
@@ -66,7 +84,7 @@ fn main() {
         print_opening(&opts);
     }
 
-    let ips: Vec<IpAddr> = parse_addresses(&opts);
+    let (ips, hostnames) = parse_addresses_with_hostnames(&opts);
 
     if ips.is_empty() {
         warning!(
@@ -78,10 +96,48 @@ fn main() {
     }
 
     #[cfg(unix)]
-    let batch_size: u16 = infer_batch_size(&opts, adjust_ulimit_size(&opts));
+    let batch_size: u32 = infer_batch_size(&opts, adjust_ulimit_size(&opts));
 
     #[cfg(not(unix))]
-    let batch_size: u16 = AVERAGE_BATCH_SIZE;
+    let batch_size: u32 = AVERAGE_BATCH_SIZE;
+
+    let jsonl_output = opts.jsonl.as_ref().map(|path| {
+        match OpenOptions::new().create(true).append(true).open(path) {
+            Ok(file) => file,
+            Err(e) => {
+                warning!(
+                    format!("Could not open JSON lines output {path:?}: {e}"),
+                    opts.greppable,
+                    opts.accessible
+                );
+                std::process::exit(1);
+            }
+        }
+    });
+
+    if let Some(proxy) = opts.proxy {
+        detail!(
+            format!("Scanning through SOCKS5 proxy {proxy}, results are as seen by the proxy."),
+            opts.greppable,
+            opts.accessible
+        );
+        if let Err(e) = block_on(socks5::check(
+            proxy,
+            Duration::from_millis(opts.timeout.into()),
+        )) {
+            warning!(
+                format!("SOCKS5 proxy {proxy} cannot be used: {e}"),
+                opts.greppable,
+                opts.accessible
+            );
+            std::process::exit(1);
+        }
+    }
+
+    let port_strategy = match opts.seed {
+        Some(seed) => PortStrategy::pick_seeded(&opts.range, opts.ports, opts.scan_order, seed),
+        None => PortStrategy::pick(&opts.range, opts.ports, opts.scan_order),
+    };
 
     let scanner = Scanner::new(
         &ips,
@@ -89,10 +145,17 @@ fn main() {
         Duration::from_millis(opts.timeout.into()),
         opts.tries,
         opts.greppable,
-        PortStrategy::pick(&opts.range, opts.ports, opts.scan_order),
+        port_strategy,
         opts.accessible,
         opts.exclude_ports.unwrap_or_default(),
         opts.udp,
+        ScanOptions {
+            max_concurrent_hosts: opts.max_concurrent_hosts,
+            first_port_only: opts.first_port_only,
+            ramp_up: opts.ramp_up,
+            proxy: opts.proxy,
+            jsonl_output,
+        },
     );
     debug!("Scanner finished building: {scanner:?}");
 
@@ -110,35 +173,45 @@ fn main() {
             .push(socket.port());
     }
 
-    for ip in ips {
-        if ports_per_ip.contains_key(&ip) {
+    for ip in &ips {
+        if ports_per_ip.contains_key(ip) {
             continue;
         }
 
         // If we got here it means the IP was not found within the HashMap, this
         // means the scan couldn't find any open ports for it.
 
-        let x = format!("Looks like I didn't find any open ports for {:?}. This is usually caused by a high batch size.
+        let x = format!("Looks like I didn't find any open ports for {}. This is usually caused by a high batch size.
         \n*I used {} batch size, consider lowering it with {} or a comfortable number for your system.
         \n Alternatively, increase the timeout if your ping is high. Rustscan -t 2000 for 2000 milliseconds (2s) timeout.\n",
-        ip,
+        host_label(ip, &hostnames),
         opts.batch_size,
         "'rustscan -b <batch_size> -a <ip address>'");
         warning!(x, opts.greppable, opts.accessible);
     }
 
     let mut script_bench = NamedTimer::start("Scripts");
-    for (ip, ports) in &ports_per_ip {
+    // Results follow the target order, so IPs resolved from the same hostname
+    // are listed together.
+    for ip in &ips {
+        let Some(ports) = ports_per_ip.get(ip) else {
+            continue;
+        };
         let vec_str_ports: Vec<String> = ports.iter().map(ToString::to_string).collect();
 
         // nmap port style is 80,443. Comma separated with no spaces.
         let ports_str = vec_str_ports.join(",");
 
         // if option scripts is none, no script will be spawned
-        if opts.greppable || opts.scripts == ScriptsRequired::None {
+        // Greppable output only lists IPs to stay easy to parse.
+        if opts.greppable {
             println!("{} -> [{}]", &ip, ports_str);
             continue;
         }
+        if opts.scripts == ScriptsRequired::None {
+            println!("{} -> [{}]", host_label(ip, &hostnames), ports_str);
+            continue;
+        }
         detail!("Starting Script(s)", opts.greppable, opts.accessible);
 
         // Run all the scripts we found and parsed based on the script config file tags field.
@@ -173,7 +246,7 @@ fn main() {
             );
             match script.run() {
                 Ok(script_result) => {
-                    detail!(script_result.to_string(), opts.greppable, opts.accessible);
+                    detail!(script_result, opts.greppable, opts.accessible);
                 }
                 Err(e) => {
                     warning!(&format!("Error {e}"), opts.greppable, opts.accessible);
@@ -246,7 +319,7 @@ fn adjust_ulimit_size(opts: &Opts) -> u64 {
 }
 
 #[cfg(unix)]
-fn infer_batch_size(opts: &Opts, ulimit: u64) -> u16 {
+fn infer_batch_size(opts: &Opts, ulimit: u64) -> u32 {
     use std::convert::TryInto;
 
     let mut batch_size: u64 = opts.batch_size.into();
@@ -283,7 +356,7 @@ fn infer_batch_size(opts: &Opts, ulimit: u64) -> u16 {
 
     batch_size
         .try_into()
-        .expect("Couldn't fit the batch size into a u16.")
+        .expect("Couldn't fit the batch size into a u32.")
 }
 
 #[cfg(test)]
@@ -1,8 +1,9 @@
 //! Provides a means to hold configuration options specifically for port scanning.
 mod range_iterator;
 use crate::input::{PortRange, ScanOrder};
-use rand::rng;
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
+use rand::SeedableRng;
 use range_iterator::RangeIterator;
 
 /// Represents options of port scanning.
@@ -18,6 +19,26 @@ pub enum PortStrategy {
 
 impl PortStrategy {
     pub fn pick(range: &Option<PortRange>, ports: Option<Vec<u16>>, order: ScanOrder) -> Self {
+        Self::pick_with_seed(range, ports, order, None)
+    }
+
+    /// Picks the strategy like `pick`, with a random order that is always
+    /// the same for the same `seed`. It has no effect on a serial order.
+    pub fn pick_seeded(
+        range: &Option<PortRange>,
+        ports: Option<Vec<u16>>,
+        order: ScanOrder,
+        seed: u64,
+    ) -> Self {
+        Self::pick_with_seed(range, ports, order, Some(seed))
+    }
+
+    fn pick_with_seed(
+        range: &Option<PortRange>,
+        ports: Option<Vec<u16>>,
+        order: ScanOrder,
+        seed: Option<u64>,
+    ) -> Self {
         match order {
             ScanOrder::Serial if ports.is_none() => {
                 let range = range.as_ref().unwrap();
@@ -31,11 +52,12 @@ impl PortStrategy {
                 PortStrategy::Random(RandomRange {
                     start: range.start,
                     end: range.end,
+                    seed,
                 })
             }
             ScanOrder::Serial => PortStrategy::Manual(ports.unwrap()),
             ScanOrder::Random => {
-                let mut rng = rng();
+                let mut rng = seeded_rng(seed);
                 let mut ports = ports.unwrap();
                 ports.shuffle(&mut rng);
                 PortStrategy::Manual(ports)
@@ -52,6 +74,12 @@ impl PortStrategy {
     }
 }
 
+/// Builds the random number generator used to randomize port orders, seeded
+/// from the OS unless a seed is given.
+fn seeded_rng(seed: Option<u64>) -> StdRng {
+    seed.map_or_else(StdRng::from_os_rng, StdRng::seed_from_u64)
+}
+
 /// Trait associated with a port strategy. Each PortStrategy must be able
 /// to generate an order for future port scanning.
 trait RangeOrder {
@@ -78,6 +106,7 @@ impl RangeOrder for SerialRange {
 pub struct RandomRange {
     start: u16,
     end: u16,
+    seed: Option<u64>,
 }
 
 impl RangeOrder for RandomRange {
@@ -91,7 +120,7 @@ impl RangeOrder for RandomRange {
     // port numbers close to each other are pretty slim due to the way the
     // algorithm works.
     fn generate(&self) -> Vec<u16> {
-        RangeIterator::new(self.start.into(), self.end.into()).collect()
+        RangeIterator::new(self.start.into(), self.end.into(), self.seed).collect()
     }
 }
 
@@ -120,6 +149,21 @@ mod tests {
         assert_eq!(expected_range, result);
     }
 
+    #[test]
+    fn random_strategy_with_seed_is_reproducible() {
+        let range = PortRange { start: 1, end: 100 };
+        let first = PortStrategy::pick_seeded(&Some(range.clone()), None, ScanOrder::Random, 42);
+        let second = PortStrategy::pick_seeded(&Some(range), None, ScanOrder::Random, 42);
+        assert_eq!(first.order(), second.order());
+
+        let ports: Vec<u16> = (1..100).collect();
+        let first = PortStrategy::pick_seeded(&None, Some(ports.clone()), ScanOrder::Random, 42);
+        let second = PortStrategy::pick_seeded(&None, Some(ports.clone()), ScanOrder::Random, 42);
+        let third = PortStrategy::pick_seeded(&None, Some(ports), ScanOrder::Random, 43);
+        assert_eq!(first.order(), second.order());
+        assert_ne!(first.order(), third.order());
+    }
+
     #[test]
     fn serial_strategy_with_ports() {
         let strategy = PortStrategy::pick(&None, Some(vec![80, 443]), ScanOrder::Serial);
@@ -1,4 +1,5 @@
 use gcd::Gcd;
+use rand::rngs::StdRng;
 use rand::Rng;
 use std::convert::TryInto;
 
@@ -21,13 +22,15 @@ impl RangeIterator {
     ///
     /// For example, the range `1000-2500` will be normalized to `0-1500`
     /// before going through the algorithm.
-    pub fn new(start: u32, end: u32) -> Self {
+    ///
+    /// The same `seed` always generates the same order.
+    pub fn new(start: u32, end: u32, seed: Option<u64>) -> Self {
+        let mut rng = super::seeded_rng(seed);
         let normalized_end = end - start + 1;
-        let step = pick_random_coprime(normalized_end);
+        let step = pick_random_coprime(normalized_end, &mut rng);
 
         // Randomly choose a number within the range to be the first
         // and assign it as a pick.
-        let mut rng = rand::rng();
         let normalized_first_pick = rng.random_range(0..normalized_end);
 
         Self {
@@ -79,11 +82,10 @@ impl Iterator for RangeIterator {
 /// the boundaries, which in these case are the "start" and "end" arguments
 /// would also provide non-ideal randomization as discussed on the paragraph
 /// above.
-fn pick_random_coprime(end: u32) -> u32 {
+fn pick_random_coprime(end: u32, rng: &mut StdRng) -> u32 {
     let range_boundary = end / 4;
     let lower_range = range_boundary;
     let upper_range = end - range_boundary;
-    let mut rng = rand::rng();
     let mut candidate = rng.random_range(lower_range..upper_range);
 
     for _ in 0..10 {
@@ -124,7 +126,7 @@ mod tests {
     }
 
     fn generate_sorted_range(start: u32, end: u32) -> Vec<u16> {
-        let range = RangeIterator::new(start, end);
+        let range = RangeIterator::new(start, end, None);
         let mut result = range.into_iter().collect::<Vec<u16>>();
         result.sort_unstable();
 
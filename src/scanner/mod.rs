@@ -1,11 +1,14 @@
 //! Core functionality for actual scanning behaviour.
 use crate::generated::get_parsed_data;
 use crate::port_strategy::PortStrategy;
+use crate::warning;
 use log::debug;
 
 mod socket_iterator;
 use socket_iterator::SocketIterator;
 
+pub mod socks5;
+
 use async_std::net::TcpStream;
 use async_std::prelude::*;
 use async_std::{io, net::UdpSocket};
@@ -14,22 +17,32 @@ use futures::stream::FuturesUnordered;
 use std::collections::BTreeMap;
 use std::{
     collections::HashSet,
+    fs::File,
+    io::{BufWriter, Write},
     net::{IpAddr, Shutdown, SocketAddr},
     num::NonZeroU8,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
+/// How many sockets are in flight at first when ramping up. The number is
+/// doubled every time that many sockets are done, until batch_size is reached.
+const RAMP_UP_START_BATCH_SIZE: u32 = 100;
+
+/// How often open ports written as JSON lines are flushed to the file.
+const JSONL_FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
 /// The class for the scanner
 /// IP is data type IpAddr and is the IP address
 /// start & end is where the port scan starts and ends
 /// batch_size is how many ports at a time should be scanned
 /// Timeout is the time RustScan should wait before declaring a port closed. As datatype Duration.
 /// greppable is whether or not RustScan should print things, or wait until the end to print only the ip and open ports.
+/// options are the optional scan behaviours, see `ScanOptions`.
 #[cfg(not(tarpaulin_include))]
 #[derive(Debug)]
 pub struct Scanner {
     ips: Vec<IpAddr>,
-    batch_size: u16,
+    batch_size: u32,
     timeout: Duration,
     tries: NonZeroU8,
     greppable: bool,
@@ -37,6 +50,34 @@ pub struct Scanner {
     accessible: bool,
     exclude_ports: Vec<u16>,
     udp: bool,
+    max_concurrent_hosts: Option<usize>,
+    first_port_only: bool,
+    ramp_up: bool,
+    proxy: Option<SocketAddr>,
+    jsonl_output: Option<File>,
+}
+
+/// Optional scan behaviours, all disabled by default.
+///
+/// ```rust
+/// # use rustscan::scanner::ScanOptions;
+/// let options = ScanOptions {
+///     first_port_only: true,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Default)]
+pub struct ScanOptions {
+    /// How many hosts at a time should be scanned, in fixed groups. None scans every host at once.
+    pub max_concurrent_hosts: Option<usize>,
+    /// Whether or not RustScan should stop scanning a host once an open port is found on it.
+    pub first_port_only: bool,
+    /// Whether or not RustScan should start with a small batch and grow it up to batch_size.
+    pub ramp_up: bool,
+    /// The SOCKS5 proxy TCP connections should go through, None connects directly.
+    pub proxy: Option<SocketAddr>,
+    /// The file each open port is appended to as a JSON line while scanning, None writes no file.
+    pub jsonl_output: Option<File>,
 }
 
 // Allowing too many arguments for clippy.
@@ -44,7 +85,7 @@ pub struct Scanner {
 impl Scanner {
     pub fn new(
         ips: &[IpAddr],
-        batch_size: u16,
+        batch_size: u32,
         timeout: Duration,
         tries: u8,
         greppable: bool,
@@ -52,7 +93,15 @@ impl Scanner {
         accessible: bool,
         exclude_ports: Vec<u16>,
         udp: bool,
+        options: ScanOptions,
     ) -> Self {
+        let ScanOptions {
+            max_concurrent_hosts,
+            first_port_only,
+            ramp_up,
+            proxy,
+            jsonl_output,
+        } = options;
         Self {
             batch_size,
             timeout,
@@ -63,6 +112,11 @@ impl Scanner {
             accessible,
             exclude_ports,
             udp,
+            max_concurrent_hosts,
+            first_port_only,
+            ramp_up,
+            proxy,
+            jsonl_output,
         }
     }
 
@@ -77,13 +131,63 @@ impl Scanner {
             .filter(|&port| !self.exclude_ports.contains(port))
             .copied()
             .collect();
-        let mut socket_iterator: SocketIterator = SocketIterator::new(&self.ips, &ports);
+        let udp_map = get_parsed_data();
+
+        // Hosts are scanned in groups of at most `max_concurrent_hosts`, the
+        // next group only starts once every socket of the previous one is done.
+        let hosts_per_group = self.max_concurrent_hosts.unwrap_or(self.ips.len()).max(1);
+
+        let mut jsonl_writer = self.jsonl_output.as_ref().map(BufWriter::new);
+        // Shared by every group, so only the first one ramps up.
+        let mut in_flight_limit = InFlightLimit::new(self.batch_size, self.ramp_up);
+
+        let mut open_sockets: Vec<SocketAddr> = Vec::new();
+        for ips in self.ips.chunks(hosts_per_group) {
+            open_sockets.extend(
+                self.scan_hosts(
+                    ips,
+                    &ports,
+                    udp_map,
+                    &mut jsonl_writer,
+                    &mut in_flight_limit,
+                )
+                .await,
+            );
+        }
+        if let Some(writer) = jsonl_writer.as_mut() {
+            if let Err(e) = writer.flush() {
+                self.jsonl_error(&e);
+            }
+        }
+        debug!("Open Sockets found: {:?}", &open_sockets);
+        open_sockets
+    }
+
+    /// Scans every port of the given hosts, keeping at most `batch_size`
+    /// sockets in flight, and returns the open ones.
+    ///
+    /// With `first_port_only`, no new sockets are started for a host once
+    /// one of its ports is open. Sockets already in flight for that host are
+    /// left to finish but their results are dropped.
+    ///
+    /// At most `in_flight_limit` sockets are in flight, which may be lower
+    /// than `batch_size` while ramping up.
+    async fn scan_hosts(
+        &self,
+        ips: &[IpAddr],
+        ports: &[u16],
+        udp_map: &BTreeMap<Vec<u16>, Vec<u8>>,
+        jsonl_writer: &mut Option<BufWriter<&File>>,
+        in_flight_limit: &mut InFlightLimit,
+    ) -> Vec<SocketAddr> {
+        let mut socket_iterator: SocketIterator = SocketIterator::new(ips, ports);
         let mut open_sockets: Vec<SocketAddr> = Vec::new();
         let mut ftrs = FuturesUnordered::new();
         let mut errors: HashSet<String> = HashSet::new();
-        let udp_map = get_parsed_data();
+        let mut hosts_done: HashSet<IpAddr> = HashSet::new();
+        let mut last_jsonl_flush = Instant::now();
 
-        for _ in 0..self.batch_size {
+        for _ in 0..in_flight_limit.get() {
             if let Some(socket) = socket_iterator.next() {
                 ftrs.push(self.scan_socket(socket, udp_map.clone()));
             } else {
@@ -93,27 +197,49 @@ impl Scanner {
 
         debug!("Start scanning sockets. \nBatch size {}\nNumber of ip-s {}\nNumber of ports {}\nTargets all together {} ",
             self.batch_size,
-            self.ips.len(),
-            &ports.len(),
-            (self.ips.len() * ports.len()));
+            ips.len(),
+            ports.len(),
+            (ips.len() * ports.len()));
 
         while let Some(result) = ftrs.next().await {
-            if let Some(socket) = socket_iterator.next() {
-                ftrs.push(self.scan_socket(socket, udp_map.clone()));
-            }
-
             match result {
-                Ok(socket) => open_sockets.push(socket),
+                Ok(socket) if self.first_port_only && !hosts_done.insert(socket.ip()) => {
+                    debug!("Dropping {socket}, an open port was already found on this host");
+                }
+                Ok(socket) => {
+                    self.fmt_ports(socket);
+                    self.write_jsonl(jsonl_writer, socket);
+                    open_sockets.push(socket);
+                }
                 Err(e) => {
                     let error_string = e.to_string();
-                    if errors.len() < self.ips.len() * 1000 {
+                    if errors.len() < ips.len() * 1000 {
                         errors.insert(error_string);
                     }
                 }
             }
+
+            if last_jsonl_flush.elapsed() >= JSONL_FLUSH_INTERVAL {
+                last_jsonl_flush = Instant::now();
+                if let Some(Err(e)) = jsonl_writer.as_mut().map(BufWriter::flush) {
+                    self.jsonl_error(&e);
+                    *jsonl_writer = None;
+                }
+            }
+
+            in_flight_limit.socket_done();
+
+            while ftrs.len() < in_flight_limit.get() as usize {
+                let Some(socket) = socket_iterator
+                    .by_ref()
+                    .find(|socket| !hosts_done.contains(&socket.ip()))
+                else {
+                    break;
+                };
+                ftrs.push(self.scan_socket(socket, udp_map.clone()));
+            }
         }
         debug!("Typical socket connection errors {errors:?}");
-        debug!("Open Sockets found: {:?}", &open_sockets);
         open_sockets
     }
 
@@ -148,10 +274,10 @@ impl Scanner {
                         "Connection was successful, shutting down stream {}",
                         &socket
                     );
+                    Self::log_source_address(tcp_stream.local_addr(), socket);
                     if let Err(e) = tcp_stream.shutdown(Shutdown::Both) {
                         debug!("Shutdown stream error {}", &e);
                     }
-                    self.fmt_ports(socket);
 
                     debug!("Return Ok after {nr_try} tries");
                     return Ok(socket);
@@ -198,7 +324,8 @@ impl Scanner {
         )))
     }
 
-    /// Performs the connection to the socket with timeout
+    /// Performs the connection to the socket with timeout, through the SOCKS5
+    /// proxy when one is set
     /// # Example
     ///
     /// ```compile_fail
@@ -213,10 +340,12 @@ impl Scanner {
     /// ```
     ///
     async fn connect(&self, socket: SocketAddr) -> io::Result<TcpStream> {
-        let stream = io::timeout(
-            self.timeout,
-            async move { TcpStream::connect(socket).await },
-        )
+        let stream = io::timeout(self.timeout, async move {
+            match self.proxy {
+                Some(proxy) => socks5::connect(proxy, socket).await,
+                None => TcpStream::connect(socket).await,
+            }
+        })
         .await?;
         Ok(stream)
     }
@@ -275,7 +404,7 @@ impl Scanner {
                 match io::timeout(wait, udp_socket.recv(&mut buf)).await {
                     Ok(size) => {
                         debug!("Received {size} bytes");
-                        self.fmt_ports(socket);
+                        Self::log_source_address(udp_socket.local_addr(), socket);
                         Ok(true)
                     }
                     Err(e) => {
@@ -294,24 +423,121 @@ impl Scanner {
         }
     }
 
+    /// Logs which local address an open port was reached from, e.g.
+    /// `10.0.0.2:54321 -> 192.168.1.5:80 open`, to help debugging NAT or
+    /// interface selection. Only shown with `RUST_LOG=debug`.
+    ///
+    /// The source address is only known when RustScan opens the socket
+    /// itself, which is the case for TCP connect and UDP scans. Through a
+    /// SOCKS5 proxy, it is the local address of the connection to the proxy.
+    fn log_source_address(local_addr: io::Result<SocketAddr>, socket: SocketAddr) {
+        match local_addr {
+            Ok(local_addr) => debug!("{local_addr} -> {socket} open"),
+            Err(e) => debug!("Source address unavailable for {socket}: {e}"),
+        }
+    }
+
+    /// Appends an open port to the JSON lines output, if any, e.g.
+    /// `{"ip":"127.0.0.1","port":80,"protocol":"tcp"}`.
+    /// The output is dropped after the first failed write.
+    fn write_jsonl(&self, jsonl_writer: &mut Option<BufWriter<&File>>, socket: SocketAddr) {
+        let Some(writer) = jsonl_writer.as_mut() else {
+            return;
+        };
+
+        let protocol = if self.udp { "udp" } else { "tcp" };
+        if let Err(e) = writeln!(
+            writer,
+            r#"{{"ip":"{}","port":{},"protocol":"{protocol}"}}"#,
+            socket.ip(),
+            socket.port()
+        ) {
+            self.jsonl_error(&e);
+            *jsonl_writer = None;
+        }
+    }
+
+    fn jsonl_error(&self, e: &std::io::Error) {
+        warning!(
+            format!("Writing open ports to the JSON lines output failed: {e}"),
+            self.greppable,
+            self.accessible
+        );
+    }
+
     /// Formats and prints the port status
     fn fmt_ports(&self, socket: SocketAddr) {
         if !self.greppable {
+            let via_proxy = self
+                .proxy
+                .filter(|_| !self.udp)
+                .map(|proxy| format!(" (via proxy {proxy})"))
+                .unwrap_or_default();
             if self.accessible {
-                println!("Open {socket}");
+                println!("Open {socket}{via_proxy}");
             } else {
-                println!("Open {}", socket.to_string().purple());
+                println!("Open {}{via_proxy}", socket.to_string().purple());
             }
         }
     }
 }
 
+/// How many sockets may be in flight at once.
+///
+/// Without ramping up this is always the batch size. When ramping up it
+/// starts at `RAMP_UP_START_BATCH_SIZE` and doubles every time that many
+/// sockets are done, until the batch size is reached.
+#[derive(Debug)]
+struct InFlightLimit {
+    limit: u32,
+    batch_size: u32,
+    done_at_limit: u32,
+}
+
+impl InFlightLimit {
+    fn new(batch_size: u32, ramp_up: bool) -> Self {
+        let limit = if ramp_up {
+            batch_size.min(RAMP_UP_START_BATCH_SIZE)
+        } else {
+            batch_size
+        };
+        Self {
+            limit,
+            batch_size,
+            done_at_limit: 0,
+        }
+    }
+
+    fn get(&self) -> u32 {
+        self.limit
+    }
+
+    /// Records that a socket is done, raising the limit once enough are.
+    fn socket_done(&mut self) {
+        if self.limit >= self.batch_size {
+            return;
+        }
+
+        self.done_at_limit += 1;
+        if self.done_at_limit == self.limit {
+            self.done_at_limit = 0;
+            self.limit = self.limit.saturating_mul(2).min(self.batch_size);
+            debug!("Ramping up batch size to {}", self.limit);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::input::{PortRange, ScanOrder};
     use async_std::task::block_on;
-    use std::{net::IpAddr, time::Duration};
+    use std::{
+        io::{Read, Write},
+        net::{IpAddr, TcpListener},
+        thread,
+        time::Duration,
+    };
 
     #[test]
     fn scanner_runs() {
@@ -332,6 +558,7 @@ mod tests {
             true,
             vec![9000],
             false,
+            ScanOptions::default(),
         );
         block_on(scanner.run());
         // if the scan fails, it wouldn't be able to assert_eq! as it panicked!
@@ -356,6 +583,7 @@ mod tests {
             true,
             vec![9000],
             false,
+            ScanOptions::default(),
         );
         block_on(scanner.run());
         // if the scan fails, it wouldn't be able to assert_eq! as it panicked!
@@ -379,6 +607,7 @@ mod tests {
             true,
             vec![9000],
             false,
+            ScanOptions::default(),
         );
         block_on(scanner.run());
         assert_eq!(1, 1);
@@ -401,6 +630,7 @@ mod tests {
             true,
             vec![9000],
             false,
+            ScanOptions::default(),
         );
         block_on(scanner.run());
         assert_eq!(1, 1);
@@ -426,6 +656,7 @@ mod tests {
             true,
             vec![9000],
             false,
+            ScanOptions::default(),
         );
         block_on(scanner.run());
         assert_eq!(1, 1);
@@ -450,6 +681,7 @@ mod tests {
             true,
             vec![9000],
             true,
+            ScanOptions::default(),
         );
         block_on(scanner.run());
         // if the scan fails, it wouldn't be able to assert_eq! as it panicked!
@@ -474,6 +706,7 @@ mod tests {
             true,
             vec![9000],
             true,
+            ScanOptions::default(),
         );
         block_on(scanner.run());
         // if the scan fails, it wouldn't be able to assert_eq! as it panicked!
@@ -497,6 +730,7 @@ mod tests {
             true,
             vec![9000],
             true,
+            ScanOptions::default(),
         );
         block_on(scanner.run());
         assert_eq!(1, 1);
@@ -519,8 +753,259 @@ mod tests {
             true,
             vec![9000],
             true,
+            ScanOptions::default(),
         );
         block_on(scanner.run());
         assert_eq!(1, 1);
     }
+
+    #[test]
+    fn max_concurrent_hosts_scans_every_host() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let addrs = vec![
+            "127.0.0.1".parse::<IpAddr>().unwrap(),
+            "::1".parse::<IpAddr>().unwrap(),
+        ];
+        let strategy = PortStrategy::pick(&None, Some(vec![port]), ScanOrder::Serial);
+        let scanner = Scanner::new(
+            &addrs,
+            10,
+            Duration::from_millis(100),
+            1,
+            true,
+            strategy,
+            true,
+            vec![],
+            false,
+            ScanOptions {
+                max_concurrent_hosts: Some(1),
+                ..Default::default()
+            },
+        );
+        let scan_result = block_on(scanner.run());
+
+        assert_eq!(scan_result, [SocketAddr::new(addrs[0], port)]);
+    }
+
+    #[test]
+    fn max_concurrent_hosts_scans_one_group_at_a_time() {
+        // Listen on the same ports of both loopback addresses, skipping the
+        // test when IPv6 is unavailable.
+        if TcpListener::bind("[::1]:0").is_err() {
+            return;
+        }
+        let mut listeners: Vec<TcpListener> = Vec::new();
+        let mut ports: Vec<u16> = Vec::new();
+        for _ in 0..100 {
+            if ports.len() == 3 {
+                break;
+            }
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let port = listener.local_addr().unwrap().port();
+            if let Ok(ipv6_listener) = TcpListener::bind(("::1", port)) {
+                listeners.extend([listener, ipv6_listener]);
+                ports.push(port);
+            }
+        }
+        assert_eq!(ports.len(), 3, "no free port on both loopback addresses");
+        let addrs = vec![
+            "127.0.0.1".parse::<IpAddr>().unwrap(),
+            "::1".parse::<IpAddr>().unwrap(),
+        ];
+        let strategy = PortStrategy::pick(&None, Some(ports.clone()), ScanOrder::Serial);
+        let scanner = Scanner::new(
+            &addrs,
+            1,
+            Duration::from_millis(100),
+            1,
+            true,
+            strategy,
+            true,
+            vec![],
+            false,
+            ScanOptions {
+                max_concurrent_hosts: Some(1),
+                ..Default::default()
+            },
+        );
+        let scan_result = block_on(scanner.run());
+
+        // Without the limit, sockets go through every host for one port
+        // before moving on to the next port.
+        let expected: Vec<SocketAddr> = addrs
+            .iter()
+            .flat_map(|&ip| ports.iter().map(move |&port| SocketAddr::new(ip, port)))
+            .collect();
+        assert_eq!(scan_result, expected);
+    }
+
+    #[test]
+    fn first_port_only_stops_at_first_open_port() {
+        let listeners: Vec<TcpListener> = (0..3)
+            .map(|_| TcpListener::bind("127.0.0.1:0").unwrap())
+            .collect();
+        let ports: Vec<u16> = listeners
+            .iter()
+            .map(|listener| listener.local_addr().unwrap().port())
+            .collect();
+        let addrs = vec!["127.0.0.1".parse::<IpAddr>().unwrap()];
+        let strategy = PortStrategy::pick(&None, Some(ports.clone()), ScanOrder::Serial);
+        let scanner = Scanner::new(
+            &addrs,
+            1,
+            Duration::from_millis(100),
+            1,
+            true,
+            strategy,
+            true,
+            vec![],
+            false,
+            ScanOptions {
+                first_port_only: true,
+                ..Default::default()
+            },
+        );
+        let scan_result = block_on(scanner.run());
+
+        assert_eq!(scan_result, [SocketAddr::new(addrs[0], ports[0])]);
+    }
+
+    #[test]
+    fn ramp_up_scans_every_port() {
+        let listeners: Vec<TcpListener> = (0..3)
+            .map(|_| TcpListener::bind("127.0.0.1:0").unwrap())
+            .collect();
+        let mut ports: Vec<u16> = listeners
+            .iter()
+            .map(|listener| listener.local_addr().unwrap().port())
+            .collect();
+        let addrs = vec!["127.0.0.1".parse::<IpAddr>().unwrap()];
+        let strategy = PortStrategy::pick(&None, Some(ports.clone()), ScanOrder::Serial);
+        let scanner = Scanner::new(
+            &addrs,
+            10,
+            Duration::from_millis(100),
+            1,
+            true,
+            strategy,
+            true,
+            vec![],
+            false,
+            ScanOptions {
+                ramp_up: true,
+                ..Default::default()
+            },
+        );
+        let mut scan_result: Vec<u16> = block_on(scanner.run())
+            .iter()
+            .map(SocketAddr::port)
+            .collect();
+
+        scan_result.sort_unstable();
+        ports.sort_unstable();
+        assert_eq!(scan_result, ports);
+    }
+
+    #[test]
+    fn ramp_up_doubles_in_flight_limit() {
+        let mut in_flight_limit = InFlightLimit::new(1_000, true);
+        let mut limits = vec![in_flight_limit.get()];
+        for _ in 0..2_000 {
+            in_flight_limit.socket_done();
+            if limits.last() != Some(&in_flight_limit.get()) {
+                limits.push(in_flight_limit.get());
+            }
+        }
+
+        assert_eq!(limits, [100, 200, 400, 800, 1_000]);
+    }
+
+    #[test]
+    fn in_flight_limit_is_batch_size_without_ramp_up() {
+        let mut in_flight_limit = InFlightLimit::new(1_000, false);
+        in_flight_limit.socket_done();
+
+        assert_eq!(in_flight_limit.get(), 1_000);
+        assert_eq!(InFlightLimit::new(10, true).get(), 10);
+    }
+
+    #[test]
+    fn proxy_scan_finds_open_port() {
+        let open_port = 8080;
+        let proxy = TcpListener::bind("127.0.0.1:0").unwrap();
+        let proxy_addr = proxy.local_addr().unwrap();
+
+        // Minimal SOCKS5 server that only reports `open_port` as reachable.
+        let server = thread::spawn(move || {
+            for stream in proxy.incoming().take(2) {
+                let mut stream = stream.unwrap();
+                let mut greeting = [0u8; 3];
+                stream.read_exact(&mut greeting).unwrap();
+                stream.write_all(&[5, 0]).unwrap();
+
+                let mut request = [0u8; 10];
+                stream.read_exact(&mut request).unwrap();
+                let port = u16::from_be_bytes([request[8], request[9]]);
+                let reply = if port == open_port { 0 } else { 5 };
+                stream
+                    .write_all(&[5, reply, 0, 1, 127, 0, 0, 1, 0, 0])
+                    .unwrap();
+            }
+        });
+
+        let addrs = vec!["192.0.2.1".parse::<IpAddr>().unwrap()];
+        let strategy = PortStrategy::pick(&None, Some(vec![22, open_port]), ScanOrder::Serial);
+        let scanner = Scanner::new(
+            &addrs,
+            10,
+            Duration::from_millis(1000),
+            1,
+            true,
+            strategy,
+            true,
+            vec![],
+            false,
+            ScanOptions {
+                proxy: Some(proxy_addr),
+                ..Default::default()
+            },
+        );
+        let scan_result = block_on(scanner.run());
+        server.join().unwrap();
+
+        assert_eq!(scan_result, [SocketAddr::new(addrs[0], open_port)]);
+    }
+
+    #[test]
+    fn jsonl_output_lists_open_ports() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let path = std::env::temp_dir().join(format!("rustscan-{port}.jsonl"));
+        let addrs = vec!["127.0.0.1".parse::<IpAddr>().unwrap()];
+        let strategy = PortStrategy::pick(&None, Some(vec![port]), ScanOrder::Serial);
+        let scanner = Scanner::new(
+            &addrs,
+            10,
+            Duration::from_millis(100),
+            1,
+            true,
+            strategy,
+            true,
+            vec![],
+            false,
+            ScanOptions {
+                jsonl_output: Some(File::create(&path).unwrap()),
+                ..Default::default()
+            },
+        );
+        block_on(scanner.run());
+        let jsonl = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            jsonl,
+            format!("{{\"ip\":\"127.0.0.1\",\"port\":{port},\"protocol\":\"tcp\"}}\n")
+        );
+    }
 }
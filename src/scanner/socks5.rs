@@ -0,0 +1,189 @@
+//! A minimal SOCKS5 client (RFC 1928) to scan through a proxy.
+use async_std::io::{self, prelude::*};
+use async_std::net::TcpStream;
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+
+const SOCKS_VERSION: u8 = 5;
+const NO_AUTHENTICATION: u8 = 0;
+const CONNECT: u8 = 1;
+const RESERVED: u8 = 0;
+const SUCCEEDED: u8 = 0;
+const ADDRESS_IPV4: u8 = 1;
+const ADDRESS_DOMAIN: u8 = 3;
+const ADDRESS_IPV6: u8 = 4;
+
+/// Opens a TCP connection to `target` through the SOCKS5 proxy at `proxy`,
+/// following RFC 1928 without authentication.
+///
+/// The connection is established once the proxy reports success, in which
+/// case the port is open. Any other reply from the proxy is returned as an
+/// error.
+pub async fn connect(proxy: SocketAddr, target: SocketAddr) -> io::Result<TcpStream> {
+    let mut stream = greet(proxy).await?;
+
+    let mut request = vec![SOCKS_VERSION, CONNECT, RESERVED];
+    match target.ip() {
+        IpAddr::V4(ip) => {
+            request.push(ADDRESS_IPV4);
+            request.extend_from_slice(&ip.octets());
+        }
+        IpAddr::V6(ip) => {
+            request.push(ADDRESS_IPV6);
+            request.extend_from_slice(&ip.octets());
+        }
+    }
+    request.extend_from_slice(&target.port().to_be_bytes());
+    stream.write_all(&request).await?;
+
+    // Reply: version, status, reserved, address type, bound address, port.
+    let mut reply = [0u8; 4];
+    stream.read_exact(&mut reply).await?;
+    if reply[0] != SOCKS_VERSION {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "{proxy} replied with SOCKS version {} instead of 5",
+                reply[0]
+            ),
+        ));
+    }
+    if reply[1] != SUCCEEDED {
+        return Err(io::Error::new(
+            io::ErrorKind::ConnectionRefused,
+            format!(
+                "SOCKS5 proxy {proxy} could not connect to {target}: {}",
+                reply_message(reply[1])
+            ),
+        ));
+    }
+
+    let address_len = match reply[3] {
+        ADDRESS_IPV4 => 4,
+        ADDRESS_IPV6 => 16,
+        ADDRESS_DOMAIN => {
+            let mut len = [0u8; 1];
+            stream.read_exact(&mut len).await?;
+            usize::from(len[0])
+        }
+        address_type => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("SOCKS5 proxy {proxy} replied with unknown address type {address_type}"),
+            ))
+        }
+    };
+    let mut bound_address = vec![0u8; address_len + 2];
+    stream.read_exact(&mut bound_address).await?;
+
+    Ok(stream)
+}
+
+/// Checks that a SOCKS5 proxy is reachable within `timeout` and accepts
+/// connections without authentication, so a scan does not report every port
+/// as closed because of the proxy.
+pub async fn check(proxy: SocketAddr, timeout: Duration) -> io::Result<()> {
+    io::timeout(timeout, greet(proxy)).await?;
+    Ok(())
+}
+
+/// Connects to the proxy and negotiates the "no authentication" method, the
+/// only one offered.
+async fn greet(proxy: SocketAddr) -> io::Result<TcpStream> {
+    let mut stream = TcpStream::connect(proxy).await?;
+
+    stream
+        .write_all(&[SOCKS_VERSION, 1, NO_AUTHENTICATION])
+        .await?;
+    let mut method = [0u8; 2];
+    stream.read_exact(&mut method).await?;
+    if method != [SOCKS_VERSION, NO_AUTHENTICATION] {
+        return Err(io::Error::other(format!(
+            "SOCKS5 proxy {proxy} requires an unsupported authentication method"
+        )));
+    }
+
+    Ok(stream)
+}
+
+/// Describes the reply codes defined by RFC 1928.
+fn reply_message(reply: u8) -> &'static str {
+    match reply {
+        1 => "general SOCKS server failure",
+        2 => "connection not allowed by ruleset",
+        3 => "network unreachable",
+        4 => "host unreachable",
+        5 => "connection refused",
+        6 => "TTL expired",
+        7 => "command not supported",
+        8 => "address type not supported",
+        _ => "unknown error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check, connect};
+    use async_std::task::block_on;
+    use std::{
+        io::{Read, Write},
+        net::{SocketAddr, TcpListener},
+        thread,
+        time::Duration,
+    };
+
+    /// Starts a proxy answering the first connection's greeting with
+    /// `method`, and its connect request with `reply`.
+    fn fake_proxy(method: [u8; 2], reply: [u8; 10]) -> SocketAddr {
+        let proxy = TcpListener::bind("127.0.0.1:0").unwrap();
+        let proxy_addr = proxy.local_addr().unwrap();
+        thread::spawn(move || {
+            let (mut stream, _) = proxy.accept().unwrap();
+            let mut greeting = [0u8; 3];
+            stream.read_exact(&mut greeting).unwrap();
+            stream.write_all(&method).unwrap();
+
+            let mut request = [0u8; 10];
+            if stream.read_exact(&mut request).is_ok() {
+                let _ = stream.write_all(&reply);
+            }
+        });
+        proxy_addr
+    }
+
+    #[test]
+    fn check_accepts_proxy_without_authentication() {
+        let proxy = fake_proxy([5, 0], [0; 10]);
+
+        assert!(block_on(check(proxy, Duration::from_secs(1))).is_ok());
+    }
+
+    #[test]
+    fn check_rejects_unreachable_proxy() {
+        let proxy = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+
+        assert!(block_on(check(proxy, Duration::from_secs(1))).is_err());
+    }
+
+    #[test]
+    fn check_rejects_proxy_requiring_authentication() {
+        let proxy = fake_proxy([5, 0xff], [0; 10]);
+
+        let error = block_on(check(proxy, Duration::from_secs(1))).unwrap_err();
+
+        assert!(error.to_string().contains("authentication"));
+    }
+
+    #[test]
+    fn connect_rejects_other_socks_versions() {
+        let proxy = fake_proxy([5, 0], [4, 0, 0, 1, 127, 0, 0, 1, 0, 80]);
+        let target = "192.0.2.1:80".parse().unwrap();
+
+        let error = block_on(connect(proxy, target)).unwrap_err();
+
+        assert!(error.to_string().contains("SOCKS version 4"));
+    }
+}